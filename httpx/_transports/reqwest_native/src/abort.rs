@@ -0,0 +1,34 @@
+use pyo3::prelude::*;
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// A cancellation token a Python caller can hold onto and use to abort a specific in-flight
+/// `NativeAsyncClient::request` call, since dropping the awaiting future alone does not
+/// reliably cancel the underlying reqwest call.
+#[pyclass]
+#[derive(Clone)]
+pub struct NativeAbortHandle {
+    notify: Arc<Notify>,
+}
+
+#[pymethods]
+impl NativeAbortHandle {
+    #[new]
+    fn py_new() -> Self {
+        NativeAbortHandle {
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    fn abort(&self) {
+        self.notify.notify_one();
+    }
+}
+
+impl NativeAbortHandle {
+    /// Resolves once `abort()` is called; pending forever otherwise so it can be used
+    /// unconditionally as one arm of a `tokio::select!`.
+    pub async fn aborted(&self) {
+        self.notify.notified().await;
+    }
+}
@@ -1,27 +1,77 @@
+use crate::abort::NativeAbortHandle;
 use crate::async_response::NativeAsyncResponse;
+use crate::decompress;
 use crate::exceptions::{
-    BadHeaderError, BadUrlError, PoolTimeoutError, SendConnectionError, SendTimeoutError,
-    SendUnknownError,
+    BadHeaderError, BadUrlError, PoolTimeoutError, RequestCancelledError, SendConnectionError,
+    SendTimeoutError, SendUnknownError, WebSocketError,
 };
+use crate::local_schemes;
 use crate::proxy_config::NativeProxyConfig;
-use crate::utils::{parse_method, parse_url};
+use crate::retry::RetryMiddleware;
+use crate::utils::{ExtensionValue, Extensions, parse_method, parse_url};
+use crate::ws::NativeAsyncWebSocket;
 use futures_util::stream::StreamExt;
 use pyo3::exceptions::{PyRuntimeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyBytes;
-use reqwest::header::{HeaderName, HeaderValue};
-use reqwest::{Body, Client};
+use reqwest::header::{ACCEPT_ENCODING, ALT_SVC, HeaderName, HeaderValue};
+use reqwest::{Body, Client, Method, redirect};
+use reqwest_middleware::{ClientBuilder as MiddlewareClientBuilder, ClientWithMiddleware};
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+/// Matches reqwest's own default redirect cap, used when `max_redirects` is omitted.
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+/// 429/502/503/504: the status codes most transient failures show up as.
+const DEFAULT_RETRYABLE_STATUSES: [u16; 4] = [429, 502, 503, 504];
+/// Methods considered safe to retry without side effects (per RFC 7231 idempotency).
+const DEFAULT_RETRYABLE_METHODS: [Method; 5] =
+    [Method::GET, Method::HEAD, Method::PUT, Method::DELETE, Method::OPTIONS];
+
+tokio::task_local! {
+    // Populated by the redirect policy closure below while a single `request()` call is in
+    // flight, so the redirect chain can be attached to the resulting response's `history`.
+    // `RetryMiddleware` clears this at the start of each retry attempt so a discarded attempt's
+    // redirects never leak into the history of the attempt that ultimately succeeds.
+    pub(crate) static REDIRECT_HISTORY: RefCell<Vec<(u16, String)>>;
+}
 
 #[pyclass]
 pub struct NativeAsyncClient {
-    client: Option<Client>,
+    client: Option<ClientWithMiddleware>,
     request_semaphore: Option<Arc<Semaphore>>,
     connect_timeout: Option<Duration>,
     #[pyo3(get)]
-    proxy: Option<NativeProxyConfig>,
+    proxies: Vec<NativeProxyConfig>,
+    /// Negotiated via `Accept-Encoding`; decoded manually in `request()` rather than via
+    /// `ClientBuilder::gzip(true)`/etc. so the original `Content-Encoding` stays observable.
+    accept_encodings: HashSet<String>,
+    /// Opt-in: `data:`/`file:` URLs otherwise raise `BadUrlError` like any other unsupported
+    /// scheme, since a client that resolves `file:` transparently gives arbitrary local-file
+    /// disclosure to whatever constructs the request (same reason browsers block `fetch()` on
+    /// `file:`).
+    allow_local_schemes: bool,
+    /// Whether custom root certificates were configured. `connect_ws` refuses to connect when
+    /// this is set, since its bare TCP/TLS connection can't honor them (see `connect_ws`).
+    has_custom_root_certificates: bool,
+    /// DER-encoded custom root certificates, kept around (alongside `connect_timeout` and
+    /// `proxies`) so `alt_svc_client` can build a matching HTTP/3 sibling client on demand.
+    root_certificates_der: Vec<Vec<u8>>,
+    /// `h3` alt-authorities (`host:port`) advertised via `Alt-Svc` on prior responses, populated
+    /// by `record_alt_svc`. `request()` consults this to send subsequent requests whose own
+    /// `host:port` is listed over HTTP/3, per the original ask that a server
+    /// advertising h3 over HTTP/1.1 "can be upgraded on subsequent requests."
+    alt_svc_hosts: Arc<std::sync::Mutex<HashSet<String>>>,
+    /// Lazily built the first time a host in `alt_svc_hosts` is requested. Built from the same
+    /// TLS/proxy configuration as `client`, but always `http3_prior_knowledge()`. Known
+    /// limitation: unlike `client`, this sibling does not run `RetryMiddleware` — a request that
+    /// fails over it is not retried; the host is only un-marked so later requests use `client`.
+    alt_svc_client: Arc<tokio::sync::Mutex<Option<ClientWithMiddleware>>>,
 }
 
 impl Drop for NativeAsyncClient {
@@ -45,11 +95,21 @@ impl NativeAsyncClient {
         http1: bool,
         http2: bool,
         root_certificates_der: Option<Vec<Vec<u8>>>,
-        proxy: Option<NativeProxyConfig>,
+        proxies: Option<Vec<NativeProxyConfig>>,
+        follow_redirects: bool,
+        max_redirects: Option<usize>,
+        max_retries: Option<u32>,
+        initial_backoff: Option<Duration>,
+        max_backoff: Option<Duration>,
+        retryable_statuses: Option<Vec<u16>>,
+        retryable_methods: Option<Vec<String>>,
+        accept_encodings: Option<Vec<String>>,
+        http3: bool,
+        allow_local_schemes: bool,
     ) -> PyResult<Self> {
-        if !http1 && !http2 {
+        if !http3 && !http1 && !http2 {
             return Err(PyValueError::new_err(
-                "At least one of http1 or http2 must be true",
+                "At least one of http1, http2 or http3 must be true",
             ));
         }
         if let Some(max_conns) = max_connections {
@@ -61,11 +121,18 @@ impl NativeAsyncClient {
         }
 
         let mut client = Client::builder();
-        if !http2 {
-            client = client.http1_only();
-        }
-        if !http1 {
-            client = client.http2_prior_knowledge();
+        if http3 {
+            // HTTP/3 is opt-in: reqwest's QUIC stack has no opportunistic upgrade, so enabling it
+            // means every connection is attempted over h3. Without it, only requests to
+            // alt-authorities recorded by `record_alt_svc` go over h3, via a separate client.
+            client = client.http3_prior_knowledge();
+        } else {
+            if !http2 {
+                client = client.http1_only();
+            }
+            if !http1 {
+                client = client.http2_prior_knowledge();
+            }
         }
         if let Some(total_timeout) = total_timeout {
             client = client.timeout(total_timeout);
@@ -82,27 +149,84 @@ impl NativeAsyncClient {
         if let Some(pool_max_idle_per_host) = pool_max_idle_per_host {
             client = client.pool_max_idle_per_host(pool_max_idle_per_host);
         }
-        if let Some(root_certificates_der) = root_certificates_der {
-            for cert in root_certificates_der {
-                client =
-                    client.add_root_certificate(reqwest::Certificate::from_der(&cert).map_err(
-                        |e| PyValueError::new_err(format!("Invalid certificate: {}", e)),
-                    )?);
-            }
+        let root_certificates_der = root_certificates_der.unwrap_or_default();
+        let has_custom_root_certificates = !root_certificates_der.is_empty();
+        for cert in &root_certificates_der {
+            client = client.add_root_certificate(
+                reqwest::Certificate::from_der(cert)
+                    .map_err(|e| PyValueError::new_err(format!("Invalid certificate: {}", e)))?,
+            );
         }
-        if let Some(proxy) = &proxy {
+        let proxies = proxies.unwrap_or_default();
+        for proxy in &proxies {
             client = client.proxy(proxy.build_reqwest_proxy()?);
         }
+        client = client.redirect(Self::build_redirect_policy(follow_redirects, max_redirects));
+
+        // Decompression is done manually in `request()` (see `decompress::decode_body`) rather
+        // than via `ClientBuilder::gzip(true)`/etc., because reqwest's own decoders strip
+        // `Content-Encoding` before the `Response` is ever handed back, making the negotiated
+        // encoding unobservable. We still advertise support via `Accept-Encoding` ourselves.
+        let accept_encodings: HashSet<String> = accept_encodings
+            .into_iter()
+            .flatten()
+            .map(|encoding| match encoding.as_str() {
+                "gzip" | "br" | "deflate" | "zstd" => Ok(encoding),
+                other => Err(PyValueError::new_err(format!(
+                    "Unsupported accept encoding: {}",
+                    other
+                ))),
+            })
+            .collect::<PyResult<_>>()?;
+        if !accept_encodings.is_empty() {
+            let value = accept_encodings.iter().cloned().collect::<Vec<_>>().join(", ");
+            let value = HeaderValue::from_str(&value)
+                .map_err(|e| PyValueError::new_err(format!("Invalid accept encoding: {}", e)))?;
+            client = client.default_headers(reqwest::header::HeaderMap::from_iter([(
+                ACCEPT_ENCODING,
+                value,
+            )]));
+        }
 
         let client = client
             .build()
             .map_err(|e| PyRuntimeError::new_err(format!("Failed to create HTTP client: {}", e)))?;
 
+        let mut middleware_client = MiddlewareClientBuilder::new(client);
+        if let Some(max_retries) = max_retries.filter(|n| *n > 0) {
+            let retryable_methods = retryable_methods
+                .map(|methods| {
+                    methods
+                        .into_iter()
+                        .map(parse_method)
+                        .collect::<PyResult<HashSet<Method>>>()
+                })
+                .transpose()?
+                .unwrap_or_else(|| HashSet::from(DEFAULT_RETRYABLE_METHODS));
+            let retryable_statuses = retryable_statuses
+                .map(|statuses| statuses.into_iter().collect())
+                .unwrap_or_else(|| HashSet::from(DEFAULT_RETRYABLE_STATUSES));
+            middleware_client = middleware_client.with(RetryMiddleware::new(
+                max_retries,
+                initial_backoff.unwrap_or(Duration::from_millis(500)),
+                max_backoff.unwrap_or(Duration::from_secs(10)),
+                retryable_statuses,
+                retryable_methods,
+            ));
+        }
+        let client = middleware_client.build();
+
         Ok(NativeAsyncClient {
             client: Some(client),
             request_semaphore: max_connections.map(|limit| Arc::new(Semaphore::new(limit))),
             connect_timeout,
-            proxy,
+            proxies,
+            accept_encodings,
+            allow_local_schemes,
+            has_custom_root_certificates,
+            root_certificates_der,
+            alt_svc_hosts: Arc::new(std::sync::Mutex::new(HashSet::new())),
+            alt_svc_client: Arc::new(tokio::sync::Mutex::new(None)),
         })
     }
 
@@ -114,6 +238,7 @@ impl NativeAsyncClient {
         headers: Option<Vec<(Vec<u8>, Vec<u8>)>>,
         content: Option<Bound<'py, PyAny>>,
         timeout: Option<Duration>,
+        abort_handle: Option<NativeAbortHandle>,
     ) -> PyResult<Bound<'py, PyAny>> {
         let client = self
             .client
@@ -122,11 +247,26 @@ impl NativeAsyncClient {
 
         let method = parse_method(method)?;
         let url = parse_url(url)?;
-        if url.scheme() != "http" && url.scheme() != "https" {
-            return Err(BadUrlError::new_err(format!(
-                "Invalid URL scheme: {}",
-                url.scheme()
-            )));
+        match url.scheme() {
+            "http" | "https" => {}
+            "data" if self.allow_local_schemes => {
+                let response = local_schemes::resolve_data_url(&url)?;
+                return pyo3_async_runtimes::tokio::future_into_py(py, async move {
+                    NativeAsyncResponse::new(response, None, Vec::new())
+                });
+            }
+            "file" if self.allow_local_schemes => {
+                return pyo3_async_runtimes::tokio::future_into_py(py, async move {
+                    let response = local_schemes::resolve_file_url(&url).await?;
+                    NativeAsyncResponse::new(response, None, Vec::new())
+                });
+            }
+            scheme => {
+                return Err(BadUrlError::new_err(format!(
+                    "Invalid URL scheme: {}",
+                    scheme
+                )));
+            }
         }
 
         let body = content
@@ -144,6 +284,140 @@ impl NativeAsyncClient {
             })
             .transpose()?;
 
+        let request_semaphore = self.request_semaphore.clone();
+        let connect_timeout = self.connect_timeout.clone();
+        let accept_encodings = self.accept_encodings.clone();
+
+        // Opportunistic HTTP/3 upgrade: if a prior response from this host advertised `h3` via
+        // `Alt-Svc` (recorded by `record_alt_svc`), route this request through the lazily-built
+        // h3 sibling client instead of the regular one.
+        let alt_svc_key = Self::alt_svc_key(&url);
+        let use_alt_svc_client = alt_svc_key
+            .as_ref()
+            .is_some_and(|key| self.alt_svc_hosts.lock().unwrap().contains(key));
+        // Only clone the h3-sibling config when it's actually going to be used — the common case
+        // is every request on a client that never saw an h3 `Alt-Svc`.
+        let alt_svc_sibling = use_alt_svc_client.then(|| {
+            (
+                self.alt_svc_client.clone(),
+                self.root_certificates_der.clone(),
+                self.proxies.clone(),
+            )
+        });
+        let alt_svc_hosts = self.alt_svc_hosts.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            REDIRECT_HISTORY
+                .scope(RefCell::new(Vec::new()), async move {
+                    let permit = if let Some(request_semaphore) = request_semaphore {
+                        Some(Self::limit_connections(request_semaphore, connect_timeout).await?)
+                    } else {
+                        None
+                    };
+
+                    let client = match alt_svc_sibling {
+                        Some((alt_svc_client, root_certificates_der, proxies)) => {
+                            Self::get_or_build_alt_svc_client(
+                                &alt_svc_client,
+                                connect_timeout,
+                                &root_certificates_der,
+                                &proxies,
+                            )
+                            .await
+                            .unwrap_or(client)
+                        }
+                        None => client,
+                    };
+
+                    let mut req_builder = client.request(method, url);
+                    if let Some(body) = body {
+                        req_builder = req_builder.body(body);
+                    }
+                    if let Some(headers) = headers {
+                        for (header_key, header_value) in headers.into_iter() {
+                            let header_name = HeaderName::from_bytes(&header_key)
+                                .map_err(|_| BadHeaderError::new_err("Invalid header key"))?;
+                            let header_value = HeaderValue::from_bytes(&header_value)
+                                .map_err(|_| BadHeaderError::new_err("Invalid header value"))?;
+                            req_builder = req_builder.header(header_name, header_value);
+                        }
+                    }
+                    if let Some(timeout) = timeout {
+                        req_builder = req_builder.timeout(timeout);
+                    }
+
+                    let request = req_builder
+                        .build()
+                        .map_err(|e| PyRuntimeError::new_err(format!("Invalid request: {}", e)))?;
+
+                    let execute_result = tokio::select! {
+                        result = client.execute(request) => result,
+                        _ = Self::wait_for_abort(&abort_handle) => {
+                            return Err(RequestCancelledError::new_err("Request was cancelled"));
+                        }
+                    };
+                    // A failure over the h3 sibling (wrong alt-authority, QUIC blocked, etc.) must
+                    // not wedge this host onto a broken path forever — un-mark it so the next
+                    // request to this host falls back to the regular client.
+                    if use_alt_svc_client && execute_result.is_err() {
+                        if let Some(key) = &alt_svc_key {
+                            alt_svc_hosts.lock().unwrap().remove(key);
+                        }
+                    }
+                    let mut response = execute_result.map_err(Self::map_send_error)?;
+                    let mut response = decompress::decode_body(response, &accept_encodings);
+                    Self::record_alt_svc(&mut response, &alt_svc_hosts);
+
+                    let history = REDIRECT_HISTORY.with(|history| history.borrow().clone());
+                    NativeAsyncResponse::new(response, permit, history)
+                })
+                .await
+        })
+    }
+
+    /// Known limitation: this dials out via `tokio_tungstenite::connect_async` on a bare TCP/TLS
+    /// connection, independent of the `reqwest::Client` built above. It does not honor
+    /// `proxies` or `root_certificates_der` configured on this client — only plain HTTP/HTTPS
+    /// requests route through those. Routing the WS upgrade through the same connector would
+    /// require driving the handshake over `reqwest`'s own connection via `hyper`'s upgrade
+    /// mechanism instead of opening a second, unrelated connection. Until that's implemented,
+    /// refuse to connect at all when either is configured rather than silently bypassing a
+    /// network policy (proxy) or a pinned CA the caller relies on.
+    fn connect_ws<'py>(
+        &self,
+        py: Python<'py>,
+        url: String,
+        headers: Option<Vec<(Vec<u8>, Vec<u8>)>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let url = parse_url(&url)?;
+        if url.scheme() != "ws" && url.scheme() != "wss" {
+            return Err(BadUrlError::new_err(format!(
+                "Invalid URL scheme: {}",
+                url.scheme()
+            )));
+        }
+        if !self.proxies.is_empty() || self.has_custom_root_certificates {
+            return Err(WebSocketError::new_err(
+                "WebSocket connections cannot honor configured proxies or custom root \
+                 certificates (connect_ws bypasses the reqwest client entirely); construct a \
+                 client without proxies/root_certificates_der to use WebSockets",
+            ));
+        }
+
+        let mut request = url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| BadUrlError::new_err(format!("Invalid WebSocket URL: {}", e)))?;
+        if let Some(headers) = headers {
+            for (header_key, header_value) in headers.into_iter() {
+                let header_name = HeaderName::from_bytes(&header_key)
+                    .map_err(|_| BadHeaderError::new_err("Invalid header key"))?;
+                let header_value = HeaderValue::from_bytes(&header_value)
+                    .map_err(|_| BadHeaderError::new_err("Invalid header value"))?;
+                request.headers_mut().insert(header_name, header_value);
+            }
+        }
+
         let request_semaphore = self.request_semaphore.clone();
         let connect_timeout = self.connect_timeout.clone();
 
@@ -154,33 +428,18 @@ impl NativeAsyncClient {
                 None
             };
 
-            let mut req_builder = client.request(method, url);
-            if let Some(body) = body {
-                req_builder = req_builder.body(body);
-            }
-            if let Some(headers) = headers {
-                for (header_key, header_value) in headers.into_iter() {
-                    let header_name = HeaderName::from_bytes(&header_key)
-                        .map_err(|_| BadHeaderError::new_err("Invalid header key"))?;
-                    let header_value = HeaderValue::from_bytes(&header_value)
-                        .map_err(|_| BadHeaderError::new_err("Invalid header value"))?;
-                    req_builder = req_builder.header(header_name, header_value);
-                }
-            }
-            if let Some(timeout) = timeout {
-                req_builder = req_builder.timeout(timeout);
-            }
-
-            let request = req_builder
-                .build()
-                .map_err(|e| PyRuntimeError::new_err(format!("Invalid request: {}", e)))?;
-
-            let response = client
-                .execute(request)
-                .await
-                .map_err(Self::map_send_error)?;
+            let connect = connect_async(request);
+            let (ws_stream, _response) = match connect_timeout {
+                Some(connect_timeout) => tokio::time::timeout(connect_timeout, connect)
+                    .await
+                    .map_err(|_| {
+                        SendTimeoutError::new_err("Timed out connecting to WebSocket server")
+                    })?
+                    .map_err(Self::map_ws_connect_error)?,
+                None => connect.await.map_err(Self::map_ws_connect_error)?,
+            };
 
-            NativeAsyncResponse::new(response, permit)
+            Ok(NativeAsyncWebSocket::new(ws_stream, permit))
         })
     }
 
@@ -194,13 +453,163 @@ impl NativeAsyncClient {
 }
 
 impl NativeAsyncClient {
-    fn map_send_error(error: reqwest::Error) -> PyErr {
-        if error.is_connect() {
-            SendConnectionError::new_err(format!("Connection error on send: {}", error))
-        } else if error.is_timeout() {
-            SendTimeoutError::new_err(format!("Timeout on send: {}", error))
-        } else {
-            SendUnknownError::new_err(format!("Unknown failure on send: {}", error))
+    fn build_redirect_policy(
+        follow_redirects: bool,
+        max_redirects: Option<usize>,
+    ) -> redirect::Policy {
+        if !follow_redirects {
+            return redirect::Policy::none();
+        }
+        let limit = max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS);
+        redirect::Policy::custom(move |attempt| {
+            let _ = REDIRECT_HISTORY.try_with(|history| {
+                if let Some(prev_url) = attempt.previous().last() {
+                    history
+                        .borrow_mut()
+                        .push((attempt.status().as_u16(), prev_url.to_string()));
+                }
+            });
+            if attempt.previous().len() >= limit {
+                attempt.error("too many redirects")
+            } else {
+                attempt.follow()
+            }
+        })
+    }
+
+    /// Surfaces a server's `Alt-Svc` advertisement (e.g. `h3=":443"`) in the response extensions,
+    /// and — when it advertises `h3` — remembers the advertised alt-authority (see
+    /// `h3_alt_svc_key`) so `request()` routes subsequent requests whose own `host:port` equals
+    /// it through the lazily-built HTTP/3 sibling client (see `get_or_build_alt_svc_client`).
+    /// Request URLs are not rewritten to the alt-authority, so an origin that advertises h3 on a
+    /// different host or port is not upgraded.
+    fn record_alt_svc(
+        response: &mut reqwest::Response,
+        alt_svc_hosts: &Arc<std::sync::Mutex<HashSet<String>>>,
+    ) {
+        let Some(alt_svc) = response
+            .headers()
+            .get(ALT_SVC)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+        else {
+            return;
+        };
+        if let Some(origin_host) = response.url().host_str() {
+            if let Some(key) = Self::h3_alt_svc_key(&alt_svc, origin_host) {
+                alt_svc_hosts.lock().unwrap().insert(key);
+            }
+        }
+        let ext = response.extensions_mut().get_or_insert_with(Extensions::new);
+        ext.insert("alt_svc".to_string(), ExtensionValue::Str(alt_svc));
+    }
+
+    /// `Alt-Svc` is a comma-separated list of alternatives, e.g. `h3=":443"; ma=2592000, h3-29=":443"`,
+    /// where the quoted value is the alt-authority `[host]:port` the alternative is actually
+    /// served on — an empty host means "same host as the response came from", and the port is
+    /// frequently *not* the origin's port. We only look for a plain `h3` entry — `h3-29`/`h3-Q...`
+    /// are older draft versions reqwest's `http3_prior_knowledge()` doesn't target — and return the
+    /// resolved `host:port` to mark in `alt_svc_hosts`, which must be the alt-authority, not the
+    /// origin's, or `request()` would dial h3 at a port nothing is listening on.
+    fn h3_alt_svc_key(alt_svc: &str, origin_host: &str) -> Option<String> {
+        for entry in alt_svc.split(',') {
+            let entry = entry.trim();
+            let field = entry.split(';').next().unwrap_or(entry).trim();
+            let Some(quoted) = field.strip_prefix("h3=") else {
+                continue;
+            };
+            let Some(authority) = quoted.strip_prefix('"').and_then(|v| v.strip_suffix('"')) else {
+                continue;
+            };
+            let (host, port) = match authority.split_once(':') {
+                Some((host, port)) => (host, port),
+                None => ("", authority),
+            };
+            if port.is_empty() {
+                continue;
+            }
+            let host = if host.is_empty() { origin_host } else { host };
+            return Some(format!("{}:{}", host, port));
+        }
+        None
+    }
+
+    /// The `host:port` key `alt_svc_hosts` is tracked and looked up under.
+    fn alt_svc_key(url: &reqwest::Url) -> Option<String> {
+        let host = url.host_str()?;
+        let port = url.port_or_known_default()?;
+        Some(format!("{}:{}", host, port))
+    }
+
+    /// Returns the cached HTTP/3 sibling client, building it on first use. The sibling shares
+    /// `proxies` and `root_certificates_der` with the main client but always dials with
+    /// `http3_prior_knowledge()`. Known limitations, kept small and explicit rather than
+    /// threading the full `py_new` config through a second builder: it does not replicate
+    /// `total_timeout`/`read_timeout`/pool tuning, and it runs without `RetryMiddleware` (a failed
+    /// request on this path is not retried — `request()` falls back to the regular client only if
+    /// *building* the sibling itself fails, not if a request over it fails).
+    async fn get_or_build_alt_svc_client(
+        alt_svc_client: &tokio::sync::Mutex<Option<ClientWithMiddleware>>,
+        connect_timeout: Option<Duration>,
+        root_certificates_der: &[Vec<u8>],
+        proxies: &[NativeProxyConfig],
+    ) -> PyResult<ClientWithMiddleware> {
+        let mut guard = alt_svc_client.lock().await;
+        if let Some(client) = guard.as_ref() {
+            return Ok(client.clone());
+        }
+
+        let mut builder = Client::builder().http3_prior_knowledge();
+        if let Some(connect_timeout) = connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        for cert in root_certificates_der {
+            builder = builder.add_root_certificate(
+                reqwest::Certificate::from_der(cert)
+                    .map_err(|e| PyRuntimeError::new_err(format!("Invalid certificate: {}", e)))?,
+            );
+        }
+        for proxy in proxies {
+            builder = builder.proxy(proxy.build_reqwest_proxy()?);
+        }
+        let client = builder.build().map_err(|e| {
+            PyRuntimeError::new_err(format!("Failed to create HTTP/3 client: {}", e))
+        })?;
+        let client = MiddlewareClientBuilder::new(client).build();
+        *guard = Some(client.clone());
+        Ok(client)
+    }
+
+    fn map_send_error(error: reqwest_middleware::Error) -> PyErr {
+        match error {
+            reqwest_middleware::Error::Reqwest(error) => {
+                if error.is_connect() {
+                    SendConnectionError::new_err(format!("Connection error on send: {}", error))
+                } else if error.is_timeout() {
+                    SendTimeoutError::new_err(format!("Timeout on send: {}", error))
+                } else {
+                    SendUnknownError::new_err(format!("Unknown failure on send: {}", error))
+                }
+            }
+            reqwest_middleware::Error::Middleware(error) => {
+                SendUnknownError::new_err(format!("Unknown failure on send: {}", error))
+            }
+        }
+    }
+
+    fn map_ws_connect_error(error: tokio_tungstenite::tungstenite::Error) -> PyErr {
+        crate::exceptions::WebSocketError::new_err(format!(
+            "Failed to establish WebSocket connection: {}",
+            error
+        ))
+    }
+
+    /// Resolves when `abort_handle` is aborted, or never if no handle was given, so it can be
+    /// used unconditionally as one arm of a `tokio::select!` around the in-flight request.
+    async fn wait_for_abort(abort_handle: &Option<NativeAbortHandle>) {
+        match abort_handle {
+            Some(handle) => handle.aborted().await,
+            None => std::future::pending().await,
         }
     }
 
@@ -1,7 +1,13 @@
-use crate::exceptions::{ReadConnectionError, ReadTimeoutError, ReadUnknownError};
+use crate::exceptions::{
+    ReadBodyError, ReadConnectionError, ReadDecodeError, ReadIncompleteMessageError,
+    ReadTimeoutError, ReadUnknownError,
+};
+use crate::lines::NativeLineStream;
+use crate::sse::NativeSseStream;
+use crate::utils::{Extensions, extensions_to_dict};
 use pyo3::exceptions::{PyRuntimeError, PyStopAsyncIteration};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
+use pyo3::types::{PyBytes, PyDict};
 use reqwest::{Response, Version};
 use std::sync::Arc;
 use tokio::sync::{Mutex, OwnedSemaphorePermit};
@@ -14,6 +20,13 @@ pub struct NativeAsyncResponse {
     headers: Vec<(Vec<u8>, Vec<u8>)>,
     #[pyo3(get)]
     http_version: String,
+    /// Status code and URL of each intermediate response that was followed via a redirect,
+    /// in the order they were visited (does not include the final response).
+    #[pyo3(get)]
+    history: Vec<(u16, String)>,
+    /// Out-of-band metadata attached by the transport layer (e.g. `content_encoding`),
+    /// mirroring the extensions dict used for request tracing.
+    extensions: Extensions,
     response: Option<Arc<Mutex<Response>>>,
     request_semaphore_permit: Option<OwnedSemaphorePermit>,
 }
@@ -30,7 +43,9 @@ impl NativeAsyncResponse {
     pub fn new(
         response: Response,
         request_semaphore_permit: Option<OwnedSemaphorePermit>,
+        history: Vec<(u16, String)>,
     ) -> PyResult<Self> {
+        let extensions = response.extensions().get::<Extensions>().cloned().unwrap_or_default();
         let response = NativeAsyncResponse {
             status: response.status().as_u16(),
             headers: response
@@ -39,6 +54,8 @@ impl NativeAsyncResponse {
                 .map(|(k, v)| (k.as_str().as_bytes().to_vec(), v.as_bytes().to_vec()))
                 .collect(),
             http_version: Self::http_version_str(response.version())?,
+            history,
+            extensions,
             response: Some(Arc::new(Mutex::new(response))),
             request_semaphore_permit,
         };
@@ -69,6 +86,60 @@ impl NativeAsyncResponse {
         })
     }
 
+    fn extensions<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        extensions_to_dict(py, &self.extensions)
+    }
+
+    /// Returns an async iterator of parsed `SseEvent`s for `text/event-stream` responses,
+    /// reading from the same underlying body stream as `__anext__`/`chunk`. Must not be used
+    /// concurrently with the raw chunk iterator on the same response.
+    fn events(&self) -> PyResult<NativeSseStream> {
+        let response = self
+            .response
+            .clone()
+            .ok_or_else(|| PyRuntimeError::new_err("Response is not initialized"))?;
+        Ok(NativeSseStream::new(response))
+    }
+
+    /// Returns an async iterator over the raw bytes of each newline-delimited (NDJSON-style)
+    /// line in the response body, reading from the same underlying stream as `__anext__`.
+    /// Must not be used concurrently with the raw chunk iterator on the same response.
+    fn lines(&self) -> PyResult<NativeLineStream> {
+        let response = self
+            .response
+            .clone()
+            .ok_or_else(|| PyRuntimeError::new_err("Response is not initialized"))?;
+        Ok(NativeLineStream::new(response))
+    }
+
+    /// Returns the HTTP/2 or HTTP/3 trailing headers, in the same `(name, value)` byte-pair
+    /// shape as `headers`. Trailers are only available once the body has been fully consumed,
+    /// so this drives the chunk stream to completion first if it hasn't been already.
+    fn trailers<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let response = self
+            .response
+            .clone()
+            .ok_or_else(|| PyRuntimeError::new_err("Response is not initialized"))?;
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut response = response.lock().await;
+            loop {
+                match response.chunk().await {
+                    Ok(Some(_)) => continue,
+                    Ok(None) => break,
+                    Err(e) => return Err(Self::map_read_error(e)),
+                }
+            }
+
+            let trailers = response.trailers().await.map_err(Self::map_read_error)?;
+            Ok(trailers
+                .unwrap_or_default()
+                .iter()
+                .map(|(k, v)| (k.as_str().as_bytes().to_vec(), v.as_bytes().to_vec()))
+                .collect::<Vec<(Vec<u8>, Vec<u8>)>>())
+        })
+    }
+
     fn close<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         if let Some(request_semaphore_permit) = self.request_semaphore_permit.take() {
             drop(request_semaphore_permit);
@@ -94,13 +165,55 @@ impl NativeAsyncResponse {
         }
     }
 
-    fn map_read_error(error: reqwest::Error) -> PyErr {
-        if error.is_connect() {
-            ReadConnectionError::new_err(format!("Connection error on read: {}", error))
+    pub(crate) fn map_read_error(error: reqwest::Error) -> PyErr {
+        let status = error.status();
+        let url = error.url().cloned();
+        let describe = |message: String| -> String {
+            let mut message = message;
+            if let Some(status) = status {
+                message.push_str(&format!(" (status {})", status.as_u16()));
+            }
+            if let Some(url) = &url {
+                message.push_str(&format!(" [{}]", url));
+            }
+            message
+        };
+
+        let source = std::error::Error::source(&error).map(|e| e.to_string());
+
+        let py_err = if error.is_connect() {
+            ReadConnectionError::new_err(describe(format!("Connection error on read: {}", error)))
         } else if error.is_timeout() {
-            ReadTimeoutError::new_err(format!("Timeout on read: {}", error))
+            ReadTimeoutError::new_err(describe(format!("Timeout on read: {}", error)))
+        } else if error.is_decode() {
+            ReadDecodeError::new_err(describe(format!(
+                "Failed to decode response body: {}",
+                error
+            )))
+        } else if Self::is_incomplete_message(&error) {
+            ReadIncompleteMessageError::new_err(describe(format!(
+                "Connection closed before the response body finished: {}",
+                error
+            )))
+        } else if error.is_body() {
+            ReadBodyError::new_err(describe(format!("Body/protocol error on read: {}", error)))
         } else {
-            ReadUnknownError::new_err(format!("Unknown failure on read: {}", error))
+            ReadUnknownError::new_err(describe(format!("Unknown failure on read: {}", error)))
+        };
+
+        if let Some(source) = source {
+            Python::with_gil(|py| {
+                py_err.set_cause(py, Some(PyRuntimeError::new_err(source)));
+            });
         }
+        py_err
+    }
+
+    /// Checks whether the failure is hyper reporting a connection that closed mid-body, which
+    /// `reqwest::Error::is_body()` also covers but doesn't distinguish on its own.
+    fn is_incomplete_message(error: &reqwest::Error) -> bool {
+        std::error::Error::source(error)
+            .and_then(|e| e.downcast_ref::<hyper::Error>())
+            .is_some_and(|e| e.is_incomplete_message())
     }
 }
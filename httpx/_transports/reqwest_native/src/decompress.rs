@@ -0,0 +1,124 @@
+use crate::utils::{Extensions, ExtensionValue};
+use async_compression::tokio::bufread::{BrotliDecoder, DeflateDecoder, GzipDecoder, ZstdDecoder};
+use futures_util::TryStreamExt;
+use reqwest::header::{CONTENT_ENCODING, CONTENT_LENGTH};
+use reqwest::{Body, Response, ResponseBuilderExt};
+use std::collections::HashSet;
+use std::io;
+use tokio::io::AsyncRead;
+use tokio_util::io::{ReaderStream, StreamReader};
+
+/// Decompresses a response body whose `Content-Encoding` is one of the codecs the client
+/// negotiated via `accept_encodings`, and stashes the original encoding in the response
+/// extensions so callers can still see how the body arrived on the wire.
+///
+/// This is deliberately done here instead of via `ClientBuilder::gzip(true)`/`brotli(true)`/etc.:
+/// reqwest's built-in decoders strip `Content-Encoding`/`Content-Length` from the response
+/// *before* handing it back, so by the time a caller (or `request()`) can inspect the headers,
+/// the very thing `content_encoding` is meant to record is already gone. Snapshotting the header
+/// here, before decoding, keeps it observable.
+pub fn decode_body(response: Response, accept_encodings: &HashSet<String>) -> Response {
+    let Some(content_encoding) = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
+    else {
+        return response;
+    };
+    if !accept_encodings.contains(content_encoding.as_str()) {
+        return response;
+    }
+
+    let status = response.status();
+    let version = response.version();
+    let url = response.url().clone();
+    let mut headers = response.headers().clone();
+    headers.remove(CONTENT_ENCODING);
+    headers.remove(CONTENT_LENGTH);
+
+    let reader = StreamReader::new(
+        response
+            .bytes_stream()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e)),
+    );
+    let decoded: std::pin::Pin<Box<dyn AsyncRead + Send>> = match content_encoding.as_str() {
+        "gzip" => Box::pin(GzipDecoder::new(reader)),
+        "br" => Box::pin(BrotliDecoder::new(reader)),
+        "deflate" => Box::pin(DeflateDecoder::new(reader)),
+        "zstd" => Box::pin(ZstdDecoder::new(reader)),
+        // `accept_encodings` only ever admits the four codecs above (validated in `py_new`).
+        _ => return response,
+    };
+
+    let mut builder = http::Response::builder()
+        .status(status)
+        .version(version)
+        .url(url);
+    *builder.headers_mut().expect("builder has no prior error") = headers;
+    let http_response = builder
+        .body(Body::wrap_stream(ReaderStream::new(decoded)))
+        .expect("building a synthetic response from known-valid parts cannot fail");
+
+    let mut response = Response::from(http_response);
+    let ext = response.extensions_mut().get_or_insert_with(Extensions::new);
+    ext.insert(
+        "content_encoding".to_string(),
+        ExtensionValue::Str(content_encoding),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_compression::tokio::write::GzipEncoder;
+    use tokio::io::AsyncWriteExt;
+
+    #[tokio::test]
+    async fn decode_body_decompresses_gzip_and_records_content_encoding() {
+        let original = b"hello world, this is a gzip-compressed response body".to_vec();
+        let mut encoder = GzipEncoder::new(Vec::new());
+        encoder.write_all(&original).await.unwrap();
+        encoder.shutdown().await.unwrap();
+        let compressed = encoder.into_inner();
+
+        let http_response = http::Response::builder()
+            .status(200)
+            .header(CONTENT_ENCODING, "gzip")
+            .header(CONTENT_LENGTH, compressed.len().to_string())
+            .body(Body::from(compressed))
+            .unwrap();
+        let response = Response::from(http_response);
+
+        let accept_encodings = HashSet::from(["gzip".to_string()]);
+        let mut decoded = decode_body(response, &accept_encodings);
+
+        assert!(decoded.headers().get(CONTENT_ENCODING).is_none());
+        assert!(decoded.headers().get(CONTENT_LENGTH).is_none());
+        match decoded.extensions_mut().get_or_insert_with(Extensions::new).get("content_encoding") {
+            Some(ExtensionValue::Str(value)) => assert_eq!(value, "gzip"),
+            other => panic!("expected a content_encoding extension, got {:?}", other),
+        }
+
+        let body = decoded.bytes().await.unwrap();
+        assert_eq!(&body[..], &original[..]);
+    }
+
+    #[tokio::test]
+    async fn decode_body_leaves_unnegotiated_encodings_untouched() {
+        let http_response = http::Response::builder()
+            .status(200)
+            .header(CONTENT_ENCODING, "gzip")
+            .body(Body::from(b"not actually gzipped".to_vec()))
+            .unwrap();
+        let response = Response::from(http_response);
+
+        let decoded = decode_body(response, &HashSet::new());
+
+        assert_eq!(
+            decoded.headers().get(CONTENT_ENCODING).unwrap(),
+            "gzip"
+        );
+    }
+}
@@ -14,3 +14,12 @@ create_exception!(module, PoolTimeoutError, PyException);
 create_exception!(module, ReadConnectionError, PyException);
 create_exception!(module, ReadTimeoutError, PyException);
 create_exception!(module, ReadUnknownError, PyException);
+create_exception!(module, ReadDecodeError, PyException);
+create_exception!(module, ReadBodyError, PyException);
+create_exception!(module, ReadIncompleteMessageError, PyException);
+
+create_exception!(module, WebSocketError, PyException);
+
+create_exception!(module, FileReadError, PyException);
+
+create_exception!(module, RequestCancelledError, PyException);
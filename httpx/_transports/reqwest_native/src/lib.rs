@@ -1,21 +1,41 @@
 // rustimport:pyo3
 
+mod abort;
 mod async_client;
 mod async_response;
+mod decompress;
 mod exceptions;
+mod lines;
+mod local_schemes;
+mod proxy_config;
+mod retry;
+mod sse;
+mod utils;
+mod ws;
 
+use crate::abort::NativeAbortHandle;
 use crate::async_client::NativeAsyncClient;
 use crate::async_response::NativeAsyncResponse;
 use crate::exceptions::{
-    BadHeaderError, BadMethodError, BadUrlError, PoolTimeoutError, ReadConnectionError,
-    ReadTimeoutError, ReadUnknownError, SendConnectionError, SendTimeoutError, SendUnknownError,
+    BadHeaderError, BadMethodError, BadUrlError, FileReadError, PoolTimeoutError,
+    ReadBodyError, ReadConnectionError, ReadDecodeError, ReadIncompleteMessageError,
+    ReadTimeoutError, ReadUnknownError, RequestCancelledError, SendConnectionError,
+    SendTimeoutError, SendUnknownError, WebSocketError,
 };
+use crate::lines::NativeLineStream;
+use crate::sse::{NativeSseStream, SseEvent};
+use crate::ws::NativeAsyncWebSocket;
 use pyo3::prelude::*;
 
 #[pymodule]
 fn reqwest_native(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_class::<NativeAsyncClient>()?;
     module.add_class::<NativeAsyncResponse>()?;
+    module.add_class::<NativeAsyncWebSocket>()?;
+    module.add_class::<NativeAbortHandle>()?;
+    module.add_class::<NativeSseStream>()?;
+    module.add_class::<SseEvent>()?;
+    module.add_class::<NativeLineStream>()?;
 
     module.add("BadMethodError", module.py().get_type::<BadMethodError>())?;
     module.add("BadUrlError", module.py().get_type::<BadUrlError>())?;
@@ -51,6 +71,21 @@ fn reqwest_native(module: &Bound<'_, PyModule>) -> PyResult<()> {
         "ReadUnknownError",
         module.py().get_type::<ReadUnknownError>(),
     )?;
+    module.add("ReadDecodeError", module.py().get_type::<ReadDecodeError>())?;
+    module.add("ReadBodyError", module.py().get_type::<ReadBodyError>())?;
+    module.add(
+        "ReadIncompleteMessageError",
+        module.py().get_type::<ReadIncompleteMessageError>(),
+    )?;
+
+    module.add("WebSocketError", module.py().get_type::<WebSocketError>())?;
+
+    module.add("FileReadError", module.py().get_type::<FileReadError>())?;
+
+    module.add(
+        "RequestCancelledError",
+        module.py().get_type::<RequestCancelledError>(),
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,87 @@
+use crate::async_response::NativeAsyncResponse;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use reqwest::Response;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[derive(Default)]
+struct LineState {
+    buf: Vec<u8>,
+    ended: bool,
+}
+
+impl LineState {
+    /// Pulls one `\n`-terminated segment out of `self.buf` (a trailing `\r` is stripped),
+    /// leaving any remainder for the next call. At end of stream, `flush` returns whatever
+    /// non-empty remainder is left as a final, unterminated line.
+    fn take_line(&mut self, flush: bool) -> Option<Vec<u8>> {
+        match self.buf.iter().position(|&b| b == b'\n') {
+            Some(pos) => {
+                let mut line: Vec<u8> = self.buf.drain(..=pos).collect();
+                line.pop(); // drop the '\n'
+                if line.last() == Some(&b'\r') {
+                    line.pop();
+                }
+                Some(line)
+            }
+            None if flush && !self.buf.is_empty() => Some(std::mem::take(&mut self.buf)),
+            None => None,
+        }
+    }
+}
+
+/// Decodes a response body as newline-delimited (NDJSON-style) records, yielding the raw bytes
+/// of each line rather than raw chunk boundaries. Wraps the same `Arc<Mutex<Response>>` that
+/// `NativeAsyncResponse` reads from, so the two must not be iterated concurrently.
+#[pyclass]
+pub struct NativeLineStream {
+    response: Arc<Mutex<Response>>,
+    state: Arc<Mutex<LineState>>,
+}
+
+impl NativeLineStream {
+    pub fn new(response: Arc<Mutex<Response>>) -> Self {
+        NativeLineStream {
+            response,
+            state: Arc::new(Mutex::new(LineState::default())),
+        }
+    }
+}
+
+#[pymethods]
+impl NativeLineStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let response = self.response.clone();
+        let state = self.state.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut state = state.lock().await;
+            loop {
+                if let Some(line) = state.take_line(false) {
+                    return Python::with_gil(|py| Ok(PyBytes::new(py, &line).unbind()));
+                }
+
+                if state.ended {
+                    return match state.take_line(true) {
+                        Some(line) => {
+                            Python::with_gil(|py| Ok(PyBytes::new(py, &line).unbind()))
+                        }
+                        None => Err(PyStopAsyncIteration::new_err("End of stream")),
+                    };
+                }
+
+                match response.lock().await.chunk().await {
+                    Ok(Some(chunk)) => state.buf.extend_from_slice(&chunk),
+                    Ok(None) => state.ended = true,
+                    Err(e) => return Err(NativeAsyncResponse::map_read_error(e)),
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,78 @@
+use crate::exceptions::{BadUrlError, FileReadError};
+use pyo3::PyErr;
+use reqwest::{Body, Response, Url};
+
+const DEFAULT_DATA_MEDIA_TYPE: &str = "text/plain;charset=US-ASCII";
+
+/// Resolves an RFC 2397 `data:` URL into a synthetic 200 response, without touching the network.
+pub fn resolve_data_url(url: &Url) -> Result<Response, PyErr> {
+    let spec = &url.as_str()[url.scheme().len() + 1..];
+    let (meta, data) = spec
+        .split_once(',')
+        .ok_or_else(|| BadUrlError::new_err("Invalid data URL: missing comma"))?;
+
+    let is_base64 = meta
+        .rsplit_once(';')
+        .is_some_and(|(_, suffix)| suffix == "base64");
+    let media_type = if is_base64 {
+        &meta[..meta.len() - ";base64".len()]
+    } else {
+        meta
+    };
+    let media_type = if media_type.is_empty() {
+        DEFAULT_DATA_MEDIA_TYPE
+    } else {
+        media_type
+    };
+
+    let body = if is_base64 {
+        use base64::Engine;
+        base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| BadUrlError::new_err(format!("Invalid base64 in data URL: {}", e)))?
+    } else {
+        percent_encoding::percent_decode_str(data).collect()
+    };
+
+    Ok(synthetic_response(200, media_type, body))
+}
+
+/// Resolves a `file:` URL by reading the local path asynchronously and wrapping it in a
+/// synthetic 200 response, mirroring what a browser-style fetch would do for local resources.
+pub async fn resolve_file_url(url: &Url) -> Result<Response, PyErr> {
+    let path = url
+        .to_file_path()
+        .map_err(|_| BadUrlError::new_err("Invalid file URL"))?;
+
+    let body = tokio::fs::read(&path)
+        .await
+        .map_err(|e| FileReadError::new_err(format!("Failed to read file {:?}: {}", path, e)))?;
+
+    Ok(synthetic_response(200, guess_media_type(&path), body))
+}
+
+fn guess_media_type(path: &std::path::Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") | Some("htm") => "text/html",
+        Some("txt") => "text/plain",
+        Some("css") => "text/css",
+        Some("js") => "text/javascript",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("pdf") => "application/pdf",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+fn synthetic_response(status: u16, content_type: &str, body: Vec<u8>) -> Response {
+    let http_response = http::Response::builder()
+        .status(status)
+        .header(reqwest::header::CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .expect("building a synthetic response from known-valid parts cannot fail");
+    Response::from(http_response)
+}
@@ -14,6 +14,12 @@ pub struct NativeProxyConfig {
     basic_auth: Option<(String, String)>,
     #[pyo3(get)]
     headers: Option<Vec<(Vec<u8>, Vec<u8>)>>,
+    /// Restricts this proxy to `http` or `https` traffic; `None` routes all schemes through it.
+    #[pyo3(get)]
+    proxy_scheme: Option<String>,
+    /// Host/domain/CIDR patterns (comma-joined for `reqwest::NoProxy`) to bypass this proxy for.
+    #[pyo3(get)]
+    no_proxy: Option<Vec<String>>,
 }
 
 #[pymethods]
@@ -23,11 +29,23 @@ impl NativeProxyConfig {
         url: String,
         basic_auth: Option<(String, String)>,
         headers: Option<Vec<(Vec<u8>, Vec<u8>)>>,
+        proxy_scheme: Option<String>,
+        no_proxy: Option<Vec<String>>,
     ) -> PyResult<Self> {
+        if let Some(scheme) = &proxy_scheme {
+            if scheme != "http" && scheme != "https" {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid proxy scheme restriction: {}",
+                    scheme
+                )));
+            }
+        }
         Ok(NativeProxyConfig {
             url,
             basic_auth,
             headers,
+            proxy_scheme,
+            no_proxy,
         })
     }
 }
@@ -46,8 +64,18 @@ impl NativeProxyConfig {
             )));
         }
 
-        let mut proxy = Proxy::all(url)
-            .map_err(|e| PyValueError::new_err(format!("Invalid Proxy URL: {}", e)))?;
+        let mut proxy = match self.proxy_scheme.as_deref() {
+            Some("http") => Proxy::http(url),
+            Some("https") => Proxy::https(url),
+            _ => Proxy::all(url),
+        }
+        .map_err(|e| PyValueError::new_err(format!("Invalid Proxy URL: {}", e)))?;
+
+        if let Some(no_proxy_patterns) = &self.no_proxy {
+            if let Some(no_proxy) = reqwest::NoProxy::from_string(&no_proxy_patterns.join(",")) {
+                proxy = proxy.no_proxy(Some(no_proxy));
+            }
+        }
 
         if let Some((username, password)) = &self.basic_auth {
             proxy = proxy.basic_auth(username, password);
@@ -0,0 +1,106 @@
+use crate::async_client::REDIRECT_HISTORY;
+use http::Extensions;
+use rand::Rng;
+use reqwest::header::HeaderValue;
+use reqwest::{Method, Request, Response, StatusCode};
+use reqwest_middleware::{Error, Middleware, Next, Result};
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Retries idempotent/transient requests with full-jitter exponential backoff, honoring
+/// `Retry-After` when the server sends one. Only requests whose body can be cloned (i.e.
+/// in-memory bodies, not `Body::wrap_stream`) are retried; streamed bodies are sent once.
+pub struct RetryMiddleware {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    retryable_statuses: HashSet<u16>,
+    retryable_methods: HashSet<Method>,
+}
+
+impl RetryMiddleware {
+    pub fn new(
+        max_retries: u32,
+        initial_backoff: Duration,
+        max_backoff: Duration,
+        retryable_statuses: HashSet<u16>,
+        retryable_methods: HashSet<Method>,
+    ) -> Self {
+        RetryMiddleware {
+            max_retries,
+            initial_backoff,
+            max_backoff,
+            retryable_statuses,
+            retryable_methods,
+        }
+    }
+
+    fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retryable_statuses.contains(&status.as_u16())
+    }
+
+    fn backoff_for_attempt(&self, attempt: u32, response: Option<&Response>) -> Duration {
+        if let Some(retry_after) = response.and_then(Self::parse_retry_after) {
+            return retry_after.min(self.max_backoff);
+        }
+        let exp = self
+            .initial_backoff
+            .saturating_mul(1 << attempt.min(31))
+            .min(self.max_backoff);
+        rand::rng().random_range(Duration::ZERO..=exp)
+    }
+
+    fn parse_retry_after(response: &Response) -> Option<Duration> {
+        let value = response.headers().get(reqwest::header::RETRY_AFTER)?;
+        Self::parse_retry_after_value(value)
+    }
+
+    fn parse_retry_after_value(value: &HeaderValue) -> Option<Duration> {
+        let value = value.to_str().ok()?;
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+        let date = httpdate::parse_http_date(value).ok()?;
+        date.duration_since(std::time::SystemTime::now()).ok()
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        if self.max_retries == 0 || !self.retryable_methods.contains(req.method()) {
+            return next.run(req, extensions).await;
+        }
+
+        let mut attempt = 0;
+        loop {
+            let Some(retryable_req) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+            // Each attempt re-triggers the client's redirect policy closure, which only ever
+            // appends. Reset before running so a discarded attempt's redirects don't leak into
+            // the history of whichever attempt ultimately wins.
+            let _ = REDIRECT_HISTORY.try_with(|history| history.borrow_mut().clear());
+            let result = next.clone().run(retryable_req, extensions).await;
+
+            let should_retry = attempt < self.max_retries
+                && match &result {
+                    Ok(response) => self.is_retryable_status(response.status()),
+                    Err(Error::Reqwest(e)) => e.is_connect() || e.is_timeout(),
+                    Err(Error::Middleware(_)) => false,
+                };
+            if !should_retry {
+                return result;
+            }
+
+            let delay = self.backoff_for_attempt(attempt, result.as_ref().ok());
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use crate::async_response::NativeAsyncResponse;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use reqwest::Response;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+const DEFAULT_EVENT: &str = "message";
+
+/// A single dispatched Server-Sent Event, per the `text/event-stream` grammar.
+#[pyclass]
+#[derive(Clone)]
+pub struct SseEvent {
+    #[pyo3(get)]
+    event: String,
+    #[pyo3(get)]
+    data: String,
+    #[pyo3(get)]
+    id: Option<String>,
+    #[pyo3(get)]
+    retry: Option<u64>,
+}
+
+#[derive(Default)]
+struct PendingEvent {
+    event: Option<String>,
+    data: String,
+    id: Option<String>,
+    retry: Option<u64>,
+    has_data: bool,
+}
+
+impl PendingEvent {
+    fn dispatch(&mut self, last_event_id: &mut Option<String>) -> SseEvent {
+        if let Some(id) = &self.id {
+            *last_event_id = Some(id.clone());
+        }
+        let event = SseEvent {
+            event: self.event.take().unwrap_or_else(|| DEFAULT_EVENT.to_string()),
+            data: self.data.strip_suffix('\n').unwrap_or(&self.data).to_string(),
+            id: last_event_id.clone(),
+            retry: self.retry,
+        };
+        *self = PendingEvent::default();
+        event
+    }
+}
+
+#[derive(Default)]
+struct SseState {
+    buf: Vec<u8>,
+    pending: PendingEvent,
+    last_event_id: Option<String>,
+    ended: bool,
+}
+
+impl SseState {
+    /// Pulls one complete line out of `self.buf` (terminated by `\n`, `\r\n`, or a lone `\r`),
+    /// leaving any trailing partial line for the next call. Returns `None` if `self.buf` has no
+    /// complete line yet, unless `flush` is set (end of stream), in which case the whole
+    /// remaining buffer is returned as a final line.
+    ///
+    /// A `\r` found as the very last byte of `self.buf` is ambiguous — it's either a lone CR
+    /// terminator or the first half of a `\r\n` pair split across two transport chunks — so it
+    /// is never committed as a terminator until either the next byte is known (more data
+    /// arrived) or `flush` confirms no more data is coming.
+    fn take_line(&mut self, flush: bool) -> Option<Vec<u8>> {
+        for i in 0..self.buf.len() {
+            match self.buf[i] {
+                b'\n' => {
+                    let mut line: Vec<u8> = self.buf.drain(..=i).collect();
+                    line.pop(); // the '\n'
+                    if line.last() == Some(&b'\r') {
+                        line.pop();
+                    }
+                    return Some(line);
+                }
+                b'\r' => match self.buf.get(i + 1) {
+                    Some(b'\n') => {
+                        let mut line: Vec<u8> = self.buf.drain(..=(i + 1)).collect();
+                        line.truncate(line.len() - 2); // the '\r\n'
+                        return Some(line);
+                    }
+                    Some(_) => {
+                        let mut line: Vec<u8> = self.buf.drain(..=i).collect();
+                        line.pop(); // the '\r'
+                        return Some(line);
+                    }
+                    None if flush => {
+                        let mut line: Vec<u8> = self.buf.drain(..=i).collect();
+                        line.pop(); // the '\r'
+                        return Some(line);
+                    }
+                    None => return None,
+                },
+                _ => {}
+            }
+        }
+        if flush && !self.buf.is_empty() {
+            return Some(std::mem::take(&mut self.buf));
+        }
+        None
+    }
+
+    /// Applies one field line of the event stream grammar. Returns `Some(event)` when a blank
+    /// line dispatches a non-empty pending event.
+    fn process_line(&mut self, line: &[u8]) -> Option<SseEvent> {
+        if line.is_empty() {
+            return if self.pending.has_data {
+                Some(self.pending.dispatch(&mut self.last_event_id))
+            } else {
+                None
+            };
+        }
+
+        let line = String::from_utf8_lossy(line);
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_ref(), ""),
+        };
+
+        match field {
+            "event" => self.pending.event = Some(value.to_string()),
+            "data" => {
+                self.pending.data.push_str(value);
+                self.pending.data.push('\n');
+                self.pending.has_data = true;
+            }
+            "id" => self.pending.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(retry) = value.parse() {
+                    self.pending.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Flushes whatever is left once the underlying stream has ended: a final partial line (if
+    /// any) followed by the pending event (if it ever received a `data:` field).
+    fn flush(&mut self) -> Option<SseEvent> {
+        if let Some(line) = self.take_line(true) {
+            if let Some(event) = self.process_line(&line) {
+                return Some(event);
+            }
+        }
+        if self.pending.has_data {
+            Some(self.pending.dispatch(&mut self.last_event_id))
+        } else {
+            None
+        }
+    }
+}
+
+/// Decodes a response body as `text/event-stream`, yielding one `SseEvent` per dispatch
+/// boundary (a blank line) instead of raw byte chunks. Wraps the same `Arc<Mutex<Response>>`
+/// that `NativeAsyncResponse` reads from, so the two must not be iterated concurrently.
+#[pyclass]
+pub struct NativeSseStream {
+    response: Arc<Mutex<Response>>,
+    state: Arc<Mutex<SseState>>,
+}
+
+impl NativeSseStream {
+    pub fn new(response: Arc<Mutex<Response>>) -> Self {
+        NativeSseStream {
+            response,
+            state: Arc::new(Mutex::new(SseState::default())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(state: &mut SseState, chunk: &[u8]) -> Vec<SseEvent> {
+        state.buf.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(line) = state.take_line(false) {
+            if let Some(event) = state.process_line(&line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn handles_crlf_terminator_split_across_transport_chunks() {
+        let mut state = SseState::default();
+        assert!(feed(&mut state, b"data: x\r").is_empty());
+        let events = feed(&mut state, b"\ndata: y\r\n\r\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data, "x\ny");
+    }
+}
+
+#[pymethods]
+impl NativeSseStream {
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let response = self.response.clone();
+        let state = self.state.clone();
+
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let mut state = state.lock().await;
+            loop {
+                if let Some(line) = state.take_line(false) {
+                    if let Some(event) = state.process_line(&line) {
+                        return Ok(event);
+                    }
+                    continue;
+                }
+
+                if state.ended {
+                    return match state.flush() {
+                        Some(event) => Ok(event),
+                        None => Err(PyStopAsyncIteration::new_err("End of stream")),
+                    };
+                }
+
+                match response.lock().await.chunk().await {
+                    Ok(Some(chunk)) => state.buf.extend_from_slice(&chunk),
+                    Ok(None) => state.ended = true,
+                    Err(e) => return Err(NativeAsyncResponse::map_read_error(e)),
+                }
+            }
+        })
+    }
+}
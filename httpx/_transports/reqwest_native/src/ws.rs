@@ -0,0 +1,131 @@
+use crate::exceptions::WebSocketError;
+use futures_util::stream::{SplitSink, SplitStream};
+use futures_util::{SinkExt, StreamExt};
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::PyStopAsyncIteration;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use std::sync::Arc;
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, OwnedSemaphorePermit};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, tungstenite::Message};
+
+type WsSink = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
+type WsStream = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+
+/// A native WebSocket connection, split into independently-lockable halves so reads and
+/// writes can proceed concurrently, mirroring how `NativeAsyncResponse` wraps its body stream.
+#[pyclass]
+pub struct NativeAsyncWebSocket {
+    sink: Arc<Mutex<WsSink>>,
+    stream: Arc<Mutex<WsStream>>,
+    request_semaphore_permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Drop for NativeAsyncWebSocket {
+    fn drop(&mut self) {
+        if let Some(permit) = self.request_semaphore_permit.take() {
+            drop(permit);
+        }
+    }
+}
+
+impl NativeAsyncWebSocket {
+    pub fn new(
+        ws_stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+        request_semaphore_permit: Option<OwnedSemaphorePermit>,
+    ) -> Self {
+        let (sink, stream) = ws_stream.split();
+        NativeAsyncWebSocket {
+            sink: Arc::new(Mutex::new(sink)),
+            stream: Arc::new(Mutex::new(stream)),
+            request_semaphore_permit,
+        }
+    }
+}
+
+#[pymethods]
+impl NativeAsyncWebSocket {
+    fn send_text<'py>(&self, py: Python<'py>, text: String) -> PyResult<Bound<'py, PyAny>> {
+        let sink = self.sink.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            sink.lock()
+                .await
+                .send(Message::Text(text.into()))
+                .await
+                .map_err(Self::map_ws_error)
+        })
+    }
+
+    fn send_bytes<'py>(&self, py: Python<'py>, data: Vec<u8>) -> PyResult<Bound<'py, PyAny>> {
+        let sink = self.sink.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            sink.lock()
+                .await
+                .send(Message::Binary(data.into()))
+                .await
+                .map_err(Self::map_ws_error)
+        })
+    }
+
+    fn recv<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let stream = self.stream.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            match Self::recv_frame(&stream).await? {
+                Some(frame) => Ok(frame),
+                None => Python::with_gil(|py| py.None().into_py_any(py)),
+            }
+        })
+    }
+
+    fn close<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        if let Some(permit) = self.request_semaphore_permit.take() {
+            drop(permit);
+        }
+        let sink = self.sink.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            sink.lock().await.close().await.map_err(Self::map_ws_error)
+        })
+    }
+
+    fn __aiter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let stream = self.stream.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            match Self::recv_frame(&stream).await? {
+                Some(frame) => Ok(frame),
+                None => Err(PyStopAsyncIteration::new_err("WebSocket closed")),
+            }
+        })
+    }
+}
+
+impl NativeAsyncWebSocket {
+    /// Waits for the next text/binary frame, transparently skipping control frames and
+    /// returning `None` once the peer closes the connection. Shared by `recv()` (which maps
+    /// `None` to `None`) and `__anext__` (which maps it to `StopAsyncIteration`).
+    async fn recv_frame(stream: &Arc<Mutex<WsStream>>) -> PyResult<Option<PyObject>> {
+        loop {
+            match stream.lock().await.next().await {
+                Some(Ok(Message::Text(text))) => {
+                    return Python::with_gil(|py| text.as_str().into_py_any(py)).map(Some);
+                }
+                Some(Ok(Message::Binary(data))) => {
+                    return Python::with_gil(|py| PyBytes::new(py, &data).into_py_any(py))
+                        .map(Some);
+                }
+                // Ping/Pong/Frame are handled transparently by tungstenite; skip them.
+                Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => continue,
+                Some(Ok(Message::Close(_))) | None => return Ok(None),
+                Some(Err(e)) => return Err(Self::map_ws_error(e)),
+            }
+        }
+    }
+
+    fn map_ws_error(error: tokio_tungstenite::tungstenite::Error) -> PyErr {
+        WebSocketError::new_err(format!("WebSocket error: {}", error))
+    }
+}